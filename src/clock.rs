@@ -0,0 +1,91 @@
+//! Pluggable time source for the debounce macros.
+//!
+//! The macros read the current time through this module instead of calling `Instant::now()`
+//! directly, so tests can install a [`MockClock`] and advance it manually instead of sleeping.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// A monotonic source of nanosecond timestamps.
+pub trait Clock: Send + Sync {
+    /// Returns nanoseconds elapsed since an arbitrary, clock-specific epoch.
+    fn now_nanos(&self) -> u64;
+}
+
+struct RealClock;
+
+/// Shared reference point for the nanosecond timestamps stored by the debounce macros.
+///
+/// An `Instant` can't be stored in an `AtomicU64` directly, so every callsite instead stores
+/// nanoseconds elapsed since this crate-wide epoch.
+static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+impl Clock for RealClock {
+    fn now_nanos(&self) -> u64 {
+        EPOCH.elapsed().as_nanos() as u64
+    }
+}
+
+/// A [`Clock`] whose time only moves when advanced manually, for deterministic tests of debounce
+/// windows.
+///
+/// Installing it via [`mock_clock`] switches every debounce macro in the process over to reading
+/// time from it, so a test can assert that a call inside the window is dropped and a call after
+/// `advance` fires.
+pub struct MockClock {
+    nanos: AtomicU64,
+}
+
+impl MockClock {
+    /// Advances the mock clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.nanos.fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+    }
+
+    /// Resets the mock clock back to zero.
+    ///
+    /// Note that each callsite's debounce state (its last-fire timestamp) is a separate `static`
+    /// that isn't reset by this call.
+    pub fn reset(&self) {
+        self.nanos.store(0, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_nanos(&self) -> u64 {
+        self.nanos.load(Ordering::SeqCst)
+    }
+}
+
+static MOCK_CLOCK: MockClock = MockClock {
+    nanos: AtomicU64::new(0),
+};
+static USE_MOCK: AtomicBool = AtomicBool::new(false);
+
+/// Returns the process-wide [`MockClock`], switching the debounce macros over to it.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::clock::mock_clock;
+/// use std::time::Duration;
+///
+/// mock_clock().reset();
+/// mock_clock().advance(Duration::from_secs(30));
+/// ```
+pub fn mock_clock() -> &'static MockClock {
+    USE_MOCK.store(true, Ordering::SeqCst);
+    &MOCK_CLOCK
+}
+
+/// Returns nanoseconds elapsed according to whichever clock is currently active, for use by the
+/// debounce macros.
+#[doc(hidden)]
+pub fn __now_nanos() -> u64 {
+    if USE_MOCK.load(Ordering::Relaxed) {
+        MOCK_CLOCK.now_nanos()
+    } else {
+        RealClock.now_nanos()
+    }
+}
@@ -14,10 +14,95 @@
 //! warn_once!("Salinometer disconnected, no measurements are available");
 //! ```
 
+pub mod clock;
+pub mod testing;
+
+/// Logs a message at the given level, debounced by duration per callsite.
+///
+/// This is the shared implementation behind `info_debounce!`, `warn_debounce!`, etc. - prefer
+/// those unless you need a level that's only known at the callsite. Subsequent calls to the same
+/// callsite within the specified duration are silently dropped, but counted; once the duration
+/// elapses the next log line reports how many were suppressed, e.g.
+/// ` (suppressed 412 messages in the last 10s)`. Each unique location in your code where this
+/// macro is invoked maintains its own debounce state.
+///
+/// Accepts an optional `target:` prefix, just like `log::info!` and friends, to log under a
+/// target other than the current module path.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::log_debounce;
+/// use std::time::Duration;
+///
+/// # let temperature = 23.5;
+/// log_debounce!(log::Level::Info, Duration::from_secs(30), "Temperature: {:.1}Â°C", temperature);
+/// log_debounce!(target: "sensors", log::Level::Info, Duration::from_secs(30), "Temperature: {:.1}Â°C", temperature);
+/// ```
+#[macro_export]
+macro_rules! log_debounce {
+    (target: $target:expr, $level:expr, $duration:expr, $($arg:tt)*) => {{
+        if log::log_enabled!(target: $target, $level) {
+            use std::sync::atomic::{AtomicU64, Ordering};
+
+            // LAST stores the fire timestamp offset by +1, with 0 reserved for "never fired": a
+            // legitimate timestamp of 0 is otherwise indistinguishable from that sentinel (it's
+            // what `clock::MockClock` starts at), which would make a callsite's first fire
+            // silently fail to debounce anything.
+            static LAST: AtomicU64 = AtomicU64::new(0);
+            static SUPPRESSED: AtomicU64 = AtomicU64::new(0);
+
+            let duration_nanos = u64::try_from($duration.as_nanos()).unwrap_or(u64::MAX);
+            let now = $crate::clock::__now_nanos();
+            let last = LAST.load(Ordering::Relaxed);
+            let should_log = last == 0 || now.saturating_sub(last - 1) >= duration_nanos;
+
+            if should_log
+                && LAST
+                    .compare_exchange(last, now.saturating_add(1), Ordering::AcqRel, Ordering::Relaxed)
+                    .is_ok()
+            {
+                let suppressed = SUPPRESSED.swap(0, Ordering::AcqRel);
+                let message = if suppressed > 0 {
+                    format!(
+                        "{} (suppressed {} message{} in the last {:?})",
+                        format_args!($($arg)*),
+                        suppressed,
+                        if suppressed == 1 { "" } else { "s" },
+                        $duration,
+                    )
+                } else {
+                    format!($($arg)*)
+                };
+
+                log::logger().log(
+                    &log::Record::builder()
+                        .args(format_args!("{}", message))
+                        .level($level)
+                        .target($target)
+                        .module_path_static(Some(module_path!()))
+                        .file_static(Some(file!()))
+                        .line(Some(line!()))
+                        .build(),
+                );
+            } else {
+                SUPPRESSED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }};
+    ($level:expr, $duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(target: module_path!(), $level, $duration, $($arg)*)
+    };
+}
+
 /// Logs a message at the info level, debounced by duration per callsite.
 ///
-/// Subsequent calls to the same callsite within the specified duration will be silently dropped.
-/// Each unique location in your code where this macro is invoked maintains its own debounce state.
+/// Subsequent calls to the same callsite within the specified duration are silently dropped, but
+/// counted; once the duration elapses the next log line reports how many were suppressed, e.g.
+/// ` (suppressed 412 messages in the last 10s)`. Each unique location in your code where this
+/// macro is invoked maintains its own debounce state.
+///
+/// Accepts an optional `target:` prefix, just like `log::info!`.
 ///
 /// # Example
 ///
@@ -28,35 +113,24 @@
 /// # let temperature = 23.5;
 /// // Will log at most once per 30 seconds from this line
 /// info_debounce!(Duration::from_secs(30), "Temperature: {:.1}Â°C", temperature);
+/// info_debounce!(target: "sensors", Duration::from_secs(30), "Temperature: {:.1}Â°C", temperature);
 /// ```
 #[macro_export]
 macro_rules! info_debounce {
-    ($duration:expr, $($arg:tt)*) => {{
-        use std::sync::LazyLock;
-        use std::sync::Mutex;
-        use std::time::Instant;
-
-        static LAST: LazyLock<Mutex<Option<Instant>>> =
-            LazyLock::new(|| Mutex::new(None));
-
-        if let Ok(mut last) = LAST.lock() {
-            let now = Instant::now();
-            let should_log = last
-                .map(|l| now.duration_since(l) >= $duration)
-                .unwrap_or(true);
-
-            if should_log {
-                *last = Some(now);
-                log::info!($($arg)*);
-            }
-        }
-    }};
+    (target: $target:expr, $duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(target: $target, log::Level::Info, $duration, $($arg)*)
+    };
+    ($duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(log::Level::Info, $duration, $($arg)*)
+    };
 }
 
 /// Logs a message at the info level exactly once per callsite.
 ///
 /// The first invocation logs the message; all subsequent calls are silently dropped.
 ///
+/// Accepts an optional `target:` prefix, just like `log::info!`.
+///
 /// # Example
 ///
 /// ```rust
@@ -67,6 +141,9 @@ macro_rules! info_debounce {
 /// ```
 #[macro_export]
 macro_rules! info_once {
+    (target: $target:expr, $($arg:tt)*) => {
+        $crate::info_debounce!(target: $target, std::time::Duration::MAX, $($arg)*)
+    };
     ($($arg:tt)*) => {
         $crate::info_debounce!(std::time::Duration::MAX, $($arg)*)
     };
@@ -74,8 +151,12 @@ macro_rules! info_once {
 
 /// Logs a message at the warn level, debounced by duration per callsite.
 ///
-/// Subsequent calls to the same callsite within the specified duration will be silently dropped.
-/// Each unique location in your code where this macro is invoked maintains its own debounce state.
+/// Subsequent calls to the same callsite within the specified duration are silently dropped, but
+/// counted; once the duration elapses the next log line reports how many were suppressed, e.g.
+/// ` (suppressed 412 messages in the last 10s)`. Each unique location in your code where this
+/// macro is invoked maintains its own debounce state.
+///
+/// Accepts an optional `target:` prefix, just like `log::warn!`.
 ///
 /// # Example
 ///
@@ -89,32 +170,20 @@ macro_rules! info_once {
 /// ```
 #[macro_export]
 macro_rules! warn_debounce {
-    ($duration:expr, $($arg:tt)*) => {{
-        use std::sync::LazyLock;
-        use std::sync::Mutex;
-        use std::time::Instant;
-
-        static LAST: LazyLock<Mutex<Option<Instant>>> =
-            LazyLock::new(|| Mutex::new(None));
-
-        if let Ok(mut last) = LAST.lock() {
-            let now = Instant::now();
-            let should_log = last
-                .map(|l| now.duration_since(l) >= $duration)
-                .unwrap_or(true);
-
-            if should_log {
-                *last = Some(now);
-                log::warn!($($arg)*);
-            }
-        }
-    }};
+    (target: $target:expr, $duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(target: $target, log::Level::Warn, $duration, $($arg)*)
+    };
+    ($duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(log::Level::Warn, $duration, $($arg)*)
+    };
 }
 
 /// Logs a message at the warn level exactly once per callsite.
 ///
 /// The first invocation logs the message; all subsequent calls are silently dropped.
 ///
+/// Accepts an optional `target:` prefix, just like `log::warn!`.
+///
 /// # Example
 ///
 /// ```rust
@@ -125,6 +194,9 @@ macro_rules! warn_debounce {
 /// ```
 #[macro_export]
 macro_rules! warn_once {
+    (target: $target:expr, $($arg:tt)*) => {
+        $crate::warn_debounce!(target: $target, std::time::Duration::MAX, $($arg)*)
+    };
     ($($arg:tt)*) => {
         $crate::warn_debounce!(std::time::Duration::MAX, $($arg)*)
     };
@@ -132,8 +204,12 @@ macro_rules! warn_once {
 
 /// Logs a message at the error level, debounced by duration per callsite.
 ///
-/// Subsequent calls to the same callsite within the specified duration will be silently dropped.
-/// Each unique location in your code where this macro is invoked maintains its own debounce state.
+/// Subsequent calls to the same callsite within the specified duration are silently dropped, but
+/// counted; once the duration elapses the next log line reports how many were suppressed, e.g.
+/// ` (suppressed 412 messages in the last 10s)`. Each unique location in your code where this
+/// macro is invoked maintains its own debounce state.
+///
+/// Accepts an optional `target:` prefix, just like `log::error!`.
 ///
 /// # Example
 ///
@@ -147,32 +223,20 @@ macro_rules! warn_once {
 /// ```
 #[macro_export]
 macro_rules! error_debounce {
-    ($duration:expr, $($arg:tt)*) => {{
-        use std::sync::LazyLock;
-        use std::sync::Mutex;
-        use std::time::Instant;
-
-        static LAST: LazyLock<Mutex<Option<Instant>>> =
-            LazyLock::new(|| Mutex::new(None));
-
-        if let Ok(mut last) = LAST.lock() {
-            let now = Instant::now();
-            let should_log = last
-                .map(|l| now.duration_since(l) >= $duration)
-                .unwrap_or(true);
-
-            if should_log {
-                *last = Some(now);
-                log::error!($($arg)*);
-            }
-        }
-    }};
+    (target: $target:expr, $duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(target: $target, log::Level::Error, $duration, $($arg)*)
+    };
+    ($duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(log::Level::Error, $duration, $($arg)*)
+    };
 }
 
 /// Logs a message at the error level exactly once per callsite.
 ///
 /// The first invocation logs the message; all subsequent calls are silently dropped.
 ///
+/// Accepts an optional `target:` prefix, just like `log::error!`.
+///
 /// # Example
 ///
 /// ```rust
@@ -183,6 +247,9 @@ macro_rules! error_debounce {
 /// ```
 #[macro_export]
 macro_rules! error_once {
+    (target: $target:expr, $($arg:tt)*) => {
+        $crate::error_debounce!(target: $target, std::time::Duration::MAX, $($arg)*)
+    };
     ($($arg:tt)*) => {
         $crate::error_debounce!(std::time::Duration::MAX, $($arg)*)
     };
@@ -190,8 +257,12 @@ macro_rules! error_once {
 
 /// Logs a message at the debug level, debounced by duration per callsite.
 ///
-/// Subsequent calls to the same callsite within the specified duration will be silently dropped.
-/// Each unique location in your code where this macro is invoked maintains its own debounce state.
+/// Subsequent calls to the same callsite within the specified duration are silently dropped, but
+/// counted; once the duration elapses the next log line reports how many were suppressed, e.g.
+/// ` (suppressed 412 messages in the last 10s)`. Each unique location in your code where this
+/// macro is invoked maintains its own debounce state.
+///
+/// Accepts an optional `target:` prefix, just like `log::debug!`.
 ///
 /// # Example
 ///
@@ -205,32 +276,20 @@ macro_rules! error_once {
 /// ```
 #[macro_export]
 macro_rules! debug_debounce {
-    ($duration:expr, $($arg:tt)*) => {{
-        use std::sync::LazyLock;
-        use std::sync::Mutex;
-        use std::time::Instant;
-
-        static LAST: LazyLock<Mutex<Option<Instant>>> =
-            LazyLock::new(|| Mutex::new(None));
-
-        if let Ok(mut last) = LAST.lock() {
-            let now = Instant::now();
-            let should_log = last
-                .map(|l| now.duration_since(l) >= $duration)
-                .unwrap_or(true);
-
-            if should_log {
-                *last = Some(now);
-                log::debug!($($arg)*);
-            }
-        }
-    }};
+    (target: $target:expr, $duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(target: $target, log::Level::Debug, $duration, $($arg)*)
+    };
+    ($duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(log::Level::Debug, $duration, $($arg)*)
+    };
 }
 
 /// Logs a message at the debug level exactly once per callsite.
 ///
 /// The first invocation logs the message; all subsequent calls are silently dropped.
 ///
+/// Accepts an optional `target:` prefix, just like `log::debug!`.
+///
 /// # Example
 ///
 /// ```rust
@@ -241,6 +300,9 @@ macro_rules! debug_debounce {
 /// ```
 #[macro_export]
 macro_rules! debug_once {
+    (target: $target:expr, $($arg:tt)*) => {
+        $crate::debug_debounce!(target: $target, std::time::Duration::MAX, $($arg)*)
+    };
     ($($arg:tt)*) => {
         $crate::debug_debounce!(std::time::Duration::MAX, $($arg)*)
     };
@@ -248,8 +310,12 @@ macro_rules! debug_once {
 
 /// Logs a message at the trace level, debounced by duration per callsite.
 ///
-/// Subsequent calls to the same callsite within the specified duration will be silently dropped.
-/// Each unique location in your code where this macro is invoked maintains its own debounce state.
+/// Subsequent calls to the same callsite within the specified duration are silently dropped, but
+/// counted; once the duration elapses the next log line reports how many were suppressed, e.g.
+/// ` (suppressed 412 messages in the last 10s)`. Each unique location in your code where this
+/// macro is invoked maintains its own debounce state.
+///
+/// Accepts an optional `target:` prefix, just like `log::trace!`.
 ///
 /// # Example
 ///
@@ -263,32 +329,20 @@ macro_rules! debug_once {
 /// ```
 #[macro_export]
 macro_rules! trace_debounce {
-    ($duration:expr, $($arg:tt)*) => {{
-        use std::sync::LazyLock;
-        use std::sync::Mutex;
-        use std::time::Instant;
-
-        static LAST: LazyLock<Mutex<Option<Instant>>> =
-            LazyLock::new(|| Mutex::new(None));
-
-        if let Ok(mut last) = LAST.lock() {
-            let now = Instant::now();
-            let should_log = last
-                .map(|l| now.duration_since(l) >= $duration)
-                .unwrap_or(true);
-
-            if should_log {
-                *last = Some(now);
-                log::trace!($($arg)*);
-            }
-        }
-    }};
+    (target: $target:expr, $duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(target: $target, log::Level::Trace, $duration, $($arg)*)
+    };
+    ($duration:expr, $($arg:tt)*) => {
+        $crate::log_debounce!(log::Level::Trace, $duration, $($arg)*)
+    };
 }
 
 /// Logs a message at the trace level exactly once per callsite.
 ///
 /// The first invocation logs the message; all subsequent calls are silently dropped.
 ///
+/// Accepts an optional `target:` prefix, just like `log::trace!`.
+///
 /// # Example
 ///
 /// ```rust
@@ -299,14 +353,231 @@ macro_rules! trace_debounce {
 /// ```
 #[macro_export]
 macro_rules! trace_once {
+    (target: $target:expr, $($arg:tt)*) => {
+        $crate::trace_debounce!(target: $target, std::time::Duration::MAX, $($arg)*)
+    };
     ($($arg:tt)*) => {
         $crate::trace_debounce!(std::time::Duration::MAX, $($arg)*)
     };
 }
 
+/// Logs a message at the given level, letting at most `max` messages through per `per` window.
+///
+/// This is the shared implementation behind `info_ratelimit!`, `warn_ratelimit!`, etc. Unlike the
+/// debounce macros, which allow exactly one message per window, this tracks a token bucket per
+/// callsite: tokens refill continuously up to `max` over the `per` duration, and each call spends
+/// one token if available. This lets the first few occurrences of a bursty event through quickly
+/// instead of collapsing them all the way down to one, while still bounding the total rate. Calls
+/// made with no tokens available are silently dropped, but counted; the next call that does find a
+/// token reports how many were dropped, e.g. ` (suppressed 7 messages in the last 60s)`.
+///
+/// Accepts an optional `target:` prefix, just like `log::info!` and friends.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::log_ratelimit;
+/// use std::time::Duration;
+///
+/// # let error = "connection reset";
+/// log_ratelimit!(log::Level::Warn, max: 5, per: Duration::from_secs(60), "Retrying: {}", error);
+/// ```
+#[macro_export]
+macro_rules! log_ratelimit {
+    (target: $target:expr, $level:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {{
+        if log::log_enabled!(target: $target, $level) {
+            use std::sync::atomic::{AtomicU64, Ordering};
+            use std::sync::Mutex;
+
+            struct TokenBucket {
+                last_refill_nanos: u64,
+                tokens: u64,
+            }
+
+            static STATE: Mutex<Option<TokenBucket>> = Mutex::new(None);
+            static SUPPRESSED: AtomicU64 = AtomicU64::new(0);
+
+            let max_tokens: u64 = $max;
+            let per_nanos = u64::try_from($per.as_nanos()).unwrap_or(u64::MAX);
+            let now = $crate::clock::__now_nanos();
+
+            let mut state = STATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let bucket = state.get_or_insert_with(|| TokenBucket {
+                last_refill_nanos: now,
+                tokens: max_tokens,
+            });
+
+            let elapsed = now.saturating_sub(bucket.last_refill_nanos);
+            if elapsed > 0 && per_nanos > 0 {
+                let refilled = (elapsed as u128 * max_tokens as u128 / per_nanos as u128) as u64;
+                if refilled > 0 {
+                    bucket.tokens = (bucket.tokens + refilled).min(max_tokens);
+                    bucket.last_refill_nanos = now;
+                }
+            }
+
+            if bucket.tokens > 0 {
+                bucket.tokens -= 1;
+                drop(state);
+
+                let suppressed = SUPPRESSED.swap(0, Ordering::AcqRel);
+                let message = if suppressed > 0 {
+                    format!(
+                        "{} (suppressed {} message{} in the last {:?})",
+                        format_args!($($arg)*),
+                        suppressed,
+                        if suppressed == 1 { "" } else { "s" },
+                        $per,
+                    )
+                } else {
+                    format!($($arg)*)
+                };
+
+                log::logger().log(
+                    &log::Record::builder()
+                        .args(format_args!("{}", message))
+                        .level($level)
+                        .target($target)
+                        .module_path_static(Some(module_path!()))
+                        .file_static(Some(file!()))
+                        .line(Some(line!()))
+                        .build(),
+                );
+            } else {
+                drop(state);
+                SUPPRESSED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }};
+    ($level:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(target: module_path!(), $level, max: $max, per: $per, $($arg)*)
+    };
+}
+
+/// Logs a message at the info level, letting at most `max` messages through per `per` window.
+///
+/// See [`log_ratelimit!`] for the full token-bucket semantics. Accepts an optional `target:`
+/// prefix, just like `log::info!`.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::info_ratelimit;
+/// use std::time::Duration;
+///
+/// # let occurrence = 1;
+/// info_ratelimit!(max: 5, per: Duration::from_secs(60), "Cache miss #{}", occurrence);
+/// ```
+#[macro_export]
+macro_rules! info_ratelimit {
+    (target: $target:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(target: $target, log::Level::Info, max: $max, per: $per, $($arg)*)
+    };
+    (max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(log::Level::Info, max: $max, per: $per, $($arg)*)
+    };
+}
+
+/// Logs a message at the warn level, letting at most `max` messages through per `per` window.
+///
+/// See [`log_ratelimit!`] for the full token-bucket semantics. Accepts an optional `target:`
+/// prefix, just like `log::warn!`.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::warn_ratelimit;
+/// use std::time::Duration;
+///
+/// # let queue_size = 1500;
+/// warn_ratelimit!(max: 5, per: Duration::from_secs(60), "Queue size high: {}", queue_size);
+/// ```
+#[macro_export]
+macro_rules! warn_ratelimit {
+    (target: $target:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(target: $target, log::Level::Warn, max: $max, per: $per, $($arg)*)
+    };
+    (max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(log::Level::Warn, max: $max, per: $per, $($arg)*)
+    };
+}
+
+/// Logs a message at the error level, letting at most `max` messages through per `per` window.
+///
+/// See [`log_ratelimit!`] for the full token-bucket semantics. Accepts an optional `target:`
+/// prefix, just like `log::error!`.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::error_ratelimit;
+/// use std::time::Duration;
+///
+/// # let error = "connection timeout";
+/// error_ratelimit!(max: 5, per: Duration::from_secs(60), "Database error: {}", error);
+/// ```
+#[macro_export]
+macro_rules! error_ratelimit {
+    (target: $target:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(target: $target, log::Level::Error, max: $max, per: $per, $($arg)*)
+    };
+    (max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(log::Level::Error, max: $max, per: $per, $($arg)*)
+    };
+}
+
+/// Logs a message at the debug level, letting at most `max` messages through per `per` window.
+///
+/// See [`log_ratelimit!`] for the full token-bucket semantics. Accepts an optional `target:`
+/// prefix, just like `log::debug!`.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::debug_ratelimit;
+/// use std::time::Duration;
+///
+/// # let cache_hits = 42;
+/// debug_ratelimit!(max: 5, per: Duration::from_secs(60), "Cache hits: {}", cache_hits);
+/// ```
+#[macro_export]
+macro_rules! debug_ratelimit {
+    (target: $target:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(target: $target, log::Level::Debug, max: $max, per: $per, $($arg)*)
+    };
+    (max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(log::Level::Debug, max: $max, per: $per, $($arg)*)
+    };
+}
+
+/// Logs a message at the trace level, letting at most `max` messages through per `per` window.
+///
+/// See [`log_ratelimit!`] for the full token-bucket semantics. Accepts an optional `target:`
+/// prefix, just like `log::trace!`.
+///
+/// # Example
+///
+/// ```rust
+/// use log_debounce::trace_ratelimit;
+/// use std::time::Duration;
+///
+/// # let iteration = 1000;
+/// trace_ratelimit!(max: 5, per: Duration::from_secs(60), "Iteration {}", iteration);
+/// ```
+#[macro_export]
+macro_rules! trace_ratelimit {
+    (target: $target:expr, max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(target: $target, log::Level::Trace, max: $max, per: $per, $($arg)*)
+    };
+    (max: $max:expr, per: $per:expr, $($arg:tt)*) => {
+        $crate::log_ratelimit!(log::Level::Trace, max: $max, per: $per, $($arg)*)
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
     use std::time::Duration;
 
     #[test]
@@ -321,4 +592,76 @@ mod tests {
         info_once!("only once");
         // both should compile, second is dropped
     }
+
+    #[test]
+    fn test_debounce_with_target() {
+        info_debounce!(target: "custom-target", Duration::from_secs(1), "test message");
+    }
+
+    // `log::set_logger` can only succeed once per process, and the mock clock is a single
+    // process-wide instance, so every test that touches either is serialized on this lock and
+    // shares the one logger, clearing it first instead of installing its own.
+    static LOGGER: testing::CapturingLogger = testing::CapturingLogger::new();
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn install_capturing_logger() -> (std::sync::MutexGuard<'static, ()>, &'static testing::CapturingLogger) {
+        let guard = TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+        LOGGER.clear();
+        (guard, &LOGGER)
+    }
+
+    #[test]
+    fn test_mock_clock_drives_debounce_window() {
+        use crate::clock::mock_clock;
+
+        let (_guard, logger) = install_capturing_logger();
+        mock_clock().reset();
+
+        // All three calls must come from the same callsite (line), since debounce state is
+        // keyed per invocation site, not per value logged.
+        for i in 0..3u32 {
+            if i == 2 {
+                mock_clock().advance(Duration::from_secs(10));
+            }
+            info_debounce!(Duration::from_secs(10), "reading: {}", i); // i=1 dropped, still in window
+        }
+
+        let messages: Vec<_> = logger.messages().into_iter().map(|m| m.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "reading: 0".to_string(),
+                "reading: 2 (suppressed 1 message in the last 10s)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ratelimit_allows_a_burst_then_throttles() {
+        use crate::clock::mock_clock;
+
+        let (_guard, logger) = install_capturing_logger();
+        mock_clock().reset();
+
+        // Same callsite for every call, since the token bucket is keyed per invocation site.
+        for i in 0..5u32 {
+            if i == 4 {
+                mock_clock().advance(Duration::from_secs(4)); // refills exactly one token
+            }
+            info_ratelimit!(max: 3, per: Duration::from_secs(10), "event {}", i); // i=3 dropped, bucket empty
+        }
+
+        let messages: Vec<_> = logger.messages().into_iter().map(|m| m.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "event 0".to_string(),
+                "event 1".to_string(),
+                "event 2".to_string(),
+                "event 4 (suppressed 1 message in the last 10s)".to_string(),
+            ]
+        );
+    }
 }
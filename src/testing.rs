@@ -0,0 +1,58 @@
+//! A minimal in-memory [`log::Log`] implementation for asserting on captured log output in tests.
+
+use log::{Level, Log, Metadata, Record};
+use std::sync::Mutex;
+
+/// A single captured log line: the level it was emitted at and its formatted message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedLog {
+    pub level: Level,
+    pub message: String,
+}
+
+/// A [`log::Log`] implementation that records every emitted record instead of printing it, so
+/// tests can assert exactly which calls fired (and with what suppressed-count suffix) and which
+/// were dropped.
+pub struct CapturingLogger {
+    records: Mutex<Vec<CapturedLog>>,
+}
+
+impl CapturingLogger {
+    /// Creates an empty capturing logger.
+    pub const fn new() -> Self {
+        Self {
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a snapshot of everything captured so far, in emission order.
+    pub fn messages(&self) -> Vec<CapturedLog> {
+        self.records.lock().unwrap().clone()
+    }
+
+    /// Clears all captured messages.
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+}
+
+impl Default for CapturingLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push(CapturedLog {
+            level: record.level(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {}
+}